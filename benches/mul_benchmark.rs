@@ -0,0 +1,39 @@
+#![feature(test)]
+
+extern crate test;
+
+use test::Bencher;
+// Benchmark the recursive Karatsuba algorithm directly rather than `arithmetic::mul`: at these
+// bitstring lengths (256/4096/65536) `arithmetic::mul` already falls back to `recursive_mul`
+// itself (they're all well above `tables::MAX_TABLED_BITS`), so calling it directly here just
+// skips a redundant length check.
+use wiedemann_tower::arithmetic::recursive_mul as mul;
+
+/// Deterministic pseudo-random element of the given tower level, for repeatable benchmarks.
+fn element(level: u32, seed: u64) -> Vec<bool> {
+    let n = 1usize << level;
+    (0..n)
+        .map(|i| seed.wrapping_mul(2654435761).wrapping_add(i as u64) & 1 == 1)
+        .collect()
+}
+
+#[bench]
+fn bench_mul_t8(b: &mut Bencher) {
+    let x = element(8, 1);
+    let y = element(8, 2);
+    b.iter(|| mul(&x, &y));
+}
+
+#[bench]
+fn bench_mul_t12(b: &mut Bencher) {
+    let x = element(12, 3);
+    let y = element(12, 4);
+    b.iter(|| mul(&x, &y));
+}
+
+#[bench]
+fn bench_mul_t16(b: &mut Bencher) {
+    let x = element(16, 5);
+    let y = element(16, 6);
+    b.iter(|| mul(&x, &y));
+}
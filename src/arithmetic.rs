@@ -1,7 +1,27 @@
 /// Given two bitstrings of length 2^i representing elements of the field of this size, return
 /// the bitstring representing their multiplication.
-/// (Efficiency could be improved using Karatsuba)
+/// Uses a log/antilog lookup table for bitstrings of length at or below
+/// `tables::MAX_TABLED_BITS`, falling back to the recursive Karatsuba algorithm above that.
 pub fn mul(left: &[bool], right: &[bool]) -> Vec<bool> {
+    let n = left.len();
+    assert_eq!(n, right.len());
+    if n == 1 {
+        return vec![left[0] & right[0]];
+    }
+    if n <= crate::tables::MAX_TABLED_BITS {
+        crate::tables::mul(left, right)
+    } else {
+        recursive_mul(left, right)
+    }
+}
+
+/// The schoolbook-avoiding Karatsuba multiplication `mul` falls back to above the table
+/// threshold, and that `tables` uses to bootstrap its log/antilog tables.
+/// Needs three recursive multiplications instead of the schoolbook four: `ac = mul(a,c)`,
+/// `bd = mul(b,d)`, `mid = mul(a+b, c+d)`. Since `mid = ac + ad + bc + bd` and the field has
+/// characteristic two, `ad + bc = mid + ac + bd`, which is all the high limb needs. This cuts
+/// the recursion from 4^k to 3^k calls.
+pub fn recursive_mul(left: &[bool], right: &[bool]) -> Vec<bool> {
     let n = left.len();
     assert_eq!(n, right.len());
     if n == 1 {
@@ -13,21 +33,75 @@ pub fn mul(left: &[bool], right: &[bool]) -> Vec<bool> {
     let b = &left[half_n..n];
     let c = &right[0..half_n];
     let d = &right[half_n..n];
-    let ac = mul(a, c);
-    let ad = mul(a, d);
-    let bc = mul(b, c);
-    let bd = mul(b, d);
-    let result_low_bits = add(&ac, &bd);
-    let result_high_bits = add(&ad, &add(&bc, &rot(&bd)));
+    let ac = recursive_mul(a, c);
+    let bd = recursive_mul(b, d);
+    let mid = recursive_mul(&add(a, b), &add(c, d));
+    let ac_plus_bd = add(&ac, &bd);
+    let result_low_bits = ac_plus_bd.clone();
+    let result_high_bits = add(&add(&mid, &ac_plus_bd), &rot(&bd));
     let mut result = vec![false; n];
     result[..half_n].copy_from_slice(&result_low_bits[..half_n]);
     result[half_n..(half_n + half_n)].copy_from_slice(&result_high_bits[..half_n]);
     result
 }
 
+/// Square a field element. Equivalent to `mul(operand, operand)`, but kept as its own entry
+/// point since squaring in a characteristic-two tower is Frobenius-linear and so admits a much
+/// cheaper specialization than general multiplication (not yet implemented here).
+pub fn square(operand: &[bool]) -> Vec<bool> {
+    mul(operand, operand)
+}
+
+/// Raise a field element to a non-negative integer power by square-and-multiply. For a non-zero
+/// `base` of length `n`, the exponent is first reduced modulo the multiplicative order `2^n - 1`,
+/// since `base^(2^n - 1) = 1`. `0^0` is an error; `0^k` for `k > 0` is `0`.
+pub fn pow(base: &[bool], exponent: u64) -> Result<Vec<bool>, String> {
+    let n = base.len();
+    if !base.iter().any(|&bit| bit) {
+        return if exponent == 0 {
+            Err("0^0 is undefined".to_string())
+        } else {
+            Ok(vec![false; n])
+        };
+    }
+    // `1 << n` overflows u128 once n reaches 128; but at that size `2^n - 1` is already far larger than
+    // any u64 exponent, so there's nothing to reduce.
+    let exponent = if n >= 128 {
+        exponent
+    } else {
+        let order = (1u128 << n) - 1;
+        (u128::from(exponent) % order) as u64
+    };
+    let mut acc = vec![false; n];
+    acc[0] = true; // the multiplicative identity
+    for i in (0..(u64::BITS - exponent.leading_zeros())).rev() {
+        acc = square(&acc);
+        if (exponent >> i) & 1 == 1 {
+            acc = mul(&acc, base);
+        }
+    }
+    Ok(acc)
+}
+
 /// Return the inverse of a non-zero field element.
-/// Algorithm from Fan & Paar: On Efficient Inversion in Tower Fields of Characteristic Two (1997)
+/// Uses a log/antilog lookup table for bitstrings of length at or below
+/// `tables::MAX_TABLED_BITS`, falling back to the recursive algorithm above that.
 pub fn inv(operand: &[bool]) -> Vec<bool> {
+    let n = operand.len();
+    assert!(operand.iter().any(|&x| x)); // zero is not invertible
+    if n == 1 {
+        return operand.into();
+    }
+    if n <= crate::tables::MAX_TABLED_BITS {
+        crate::tables::inv(operand)
+    } else {
+        recursive_inv(operand)
+    }
+}
+
+/// Algorithm from Fan & Paar: On Efficient Inversion in Tower Fields of Characteristic Two (1997).
+/// What `inv` falls back to above the table threshold.
+pub fn recursive_inv(operand: &[bool]) -> Vec<bool> {
     let n = operand.len();
     assert!(operand.iter().any(|&x| x)); // zero is not invertible
     if n == 1 {
@@ -39,12 +113,12 @@ pub fn inv(operand: &[bool]) -> Vec<bool> {
     let high_bits = &operand[half_n..n];
     let rot_high_bits = rot(high_bits);
     let delta = &add(
-        &mul(low_bits, &add(low_bits, &rot_high_bits)),
-        &mul(high_bits, high_bits),
+        &recursive_mul(low_bits, &add(low_bits, &rot_high_bits)),
+        &recursive_mul(high_bits, high_bits),
     );
-    let delta_inv = &inv(delta);
-    let result_high_bits = mul(delta_inv, high_bits);
-    let result_low_bits = mul(delta_inv, &add(low_bits, &rot_high_bits));
+    let delta_inv = &recursive_inv(delta);
+    let result_high_bits = recursive_mul(delta_inv, high_bits);
+    let result_low_bits = recursive_mul(delta_inv, &add(low_bits, &rot_high_bits));
     let mut result = vec![false; n];
     result[..half_n].copy_from_slice(&result_low_bits[..half_n]);
     result[half_n..(half_n + half_n)].copy_from_slice(&result_high_bits[..half_n]);
@@ -85,6 +159,33 @@ pub fn rot(operand: &[bool]) -> Vec<bool> {
     result
 }
 
+#[test]
+fn test_pow() {
+    let x2 = vec![false, false, false, false, true, false, false, false];
+    // x2^2 via square-and-multiply should match plain squaring
+    assert_eq!(pow(&x2, 2).unwrap(), square(&x2));
+    // x^0 = 1
+    let one = vec![true, false, false, false, false, false, false, false];
+    assert_eq!(pow(&x2, 0).unwrap(), one);
+    // x^(2^n - 1) = 1 for non-zero x
+    assert_eq!(pow(&x2, 255).unwrap(), one);
+    // 0^0 is an error, but 0^k for k > 0 is 0
+    let zero = vec![false; 8];
+    assert!(pow(&zero, 0).is_err());
+    assert_eq!(pow(&zero, 5).unwrap(), zero);
+}
+
+#[test]
+fn test_pow_n_128() {
+    // At n = 128, `1 << n` overflows u128; pow must not compute `2^n - 1` that way.
+    let mut x = vec![false; 128];
+    x[64] = true;
+    assert_eq!(pow(&x, 2).unwrap(), square(&x));
+    let mut one = vec![false; 128];
+    one[0] = true;
+    assert_eq!(pow(&x, 0).unwrap(), one);
+}
+
 #[test]
 fn test_mul() {
     // test some base cases
@@ -0,0 +1,152 @@
+/// Textual encodings for tower elements, for use when the raw `0`/`1` bitstring becomes
+/// unreadable (a `T16` element is already 65536 bits long).
+///
+/// Bits are packed little-endian into bytes: bit `i` lives in byte `i / 8`, bit `i % 8`. Because
+/// tower elements have length a power of two and not necessarily a multiple of eight, the decoded
+/// byte count alone can't tell you where the element actually ends, so the exact bit length is
+/// always carried alongside the bytes.
+const BASE64_ALPHABET: &[u8; 64] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// Pack a bitstring into little-endian bytes, padding the final byte with `false` bits.
+pub fn pack_bits(bits: &[bool]) -> Vec<u8> {
+    let mut bytes = vec![0u8; bits.len().div_ceil(8)];
+    for (i, &bit) in bits.iter().enumerate() {
+        if bit {
+            bytes[i / 8] |= 1 << (i % 8);
+        }
+    }
+    bytes
+}
+
+/// Unpack the given exact number of bits from little-endian bytes.
+pub fn unpack_bits(bytes: &[u8], bit_len: usize) -> Result<Vec<bool>, String> {
+    if bit_len > bytes.len() * 8 {
+        return Err(format!(
+            "Only {} bits are available but {} were requested",
+            bytes.len() * 8,
+            bit_len
+        ));
+    }
+    Ok((0..bit_len).map(|i| bytes[i / 8] & (1 << (i % 8)) != 0).collect())
+}
+
+/// Encode a bitstring as a hex string of its packed bytes.
+pub fn encode_hex(bits: &[bool]) -> String {
+    pack_bits(bits).iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+/// Decode a hex string of packed bytes into a bitstring of the given exact bit length. An odd
+/// number of digits is padded with a leading zero nibble, so that a single digit like `f` (the
+/// natural way to write a T2/4-bit element) decodes rather than being rejected.
+pub fn decode_hex(hex: &str, bit_len: usize) -> Result<Vec<bool>, String> {
+    let padded;
+    let hex = if !hex.len().is_multiple_of(2) {
+        padded = format!("0{}", hex);
+        padded.as_str()
+    } else {
+        hex
+    };
+    let bytes = (0..hex.len())
+        .step_by(2)
+        .map(|i| {
+            u8::from_str_radix(&hex[i..i + 2], 16)
+                .map_err(|_| format!("Invalid hex digits in '{}'", hex))
+        })
+        .collect::<Result<Vec<u8>, String>>()?;
+    unpack_bits(&bytes, bit_len)
+}
+
+/// Encode a bitstring as a standard (RFC 4648) base64 string of its packed bytes.
+pub fn encode_base64(bits: &[bool]) -> String {
+    let bytes = pack_bits(bits);
+    let mut out = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let b0 = chunk[0];
+        let b1 = chunk.get(1).copied().unwrap_or(0);
+        let b2 = chunk.get(2).copied().unwrap_or(0);
+        out.push(BASE64_ALPHABET[(b0 >> 2) as usize] as char);
+        out.push(BASE64_ALPHABET[(((b0 & 0x03) << 4) | (b1 >> 4)) as usize] as char);
+        out.push(if chunk.len() > 1 {
+            BASE64_ALPHABET[(((b1 & 0x0f) << 2) | (b2 >> 6)) as usize] as char
+        } else {
+            '='
+        });
+        out.push(if chunk.len() > 2 {
+            BASE64_ALPHABET[(b2 & 0x3f) as usize] as char
+        } else {
+            '='
+        });
+    }
+    out
+}
+
+/// Decode a standard (RFC 4648) base64 string of packed bytes into a bitstring of the given
+/// exact bit length.
+pub fn decode_base64(b64: &str, bit_len: usize) -> Result<Vec<bool>, String> {
+    let digits = b64
+        .bytes()
+        .filter(|&b| b != b'=')
+        .map(|b| {
+            BASE64_ALPHABET
+                .iter()
+                .position(|&a| a == b)
+                .map(|p| p as u8)
+                .ok_or_else(|| format!("Invalid base64 character '{}'", b as char))
+        })
+        .collect::<Result<Vec<u8>, String>>()?;
+    let mut bytes = Vec::with_capacity(digits.len() * 3 / 4);
+    for chunk in digits.chunks(4) {
+        bytes.push((chunk[0] << 2) | (chunk.get(1).copied().unwrap_or(0) >> 4));
+        if chunk.len() > 2 {
+            bytes.push((chunk[1] << 4) | (chunk[2] >> 2));
+        }
+        if chunk.len() > 3 {
+            bytes.push((chunk[2] << 6) | chunk[3]);
+        }
+    }
+    unpack_bits(&bytes, bit_len)
+}
+
+#[test]
+fn test_pack_unpack_roundtrip() {
+    let bits = vec![true, false, false, true, true, false, true, false, true];
+    let bytes = pack_bits(&bits);
+    assert_eq!(unpack_bits(&bytes, bits.len()).unwrap(), bits);
+}
+
+#[test]
+fn test_hex_roundtrip() {
+    let bits = vec![true, false, true, false, false, false, false, false];
+    let hex = encode_hex(&bits);
+    assert_eq!(hex, "05");
+    assert_eq!(decode_hex(&hex, bits.len()).unwrap(), bits);
+}
+
+#[test]
+fn test_hex_short_bit_length() {
+    // T1 (length 2) packed into a single byte, with the unused high bits discarded on decode.
+    let bits = vec![true, false];
+    let hex = encode_hex(&bits);
+    assert_eq!(decode_hex(&hex, 2).unwrap(), bits);
+}
+
+#[test]
+fn test_hex_odd_digit_count() {
+    // A single hex digit, the natural way to write a T2 (4-bit) element, should pad rather
+    // than error.
+    assert_eq!(
+        decode_hex("f", 4).unwrap(),
+        vec![true, true, true, true]
+    );
+}
+
+#[test]
+fn test_base64_roundtrip() {
+    let bits = vec![
+        true, false, true, false, false, false, false, false, false, true, true, false, false,
+        false, false, false,
+    ];
+    let b64 = encode_base64(&bits);
+    assert_eq!(decode_base64(&b64, bits.len()).unwrap(), bits);
+}
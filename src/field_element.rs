@@ -0,0 +1,134 @@
+use std::cmp::max;
+use std::ops::{Add, Div, Mul};
+
+use crate::arithmetic::{add, inv, mul};
+
+/// An element of the Wiedemann tower, as a bitstring of length `2^i` for its level `i`.
+///
+/// Construction validates that the bitstring length is a power of two, so every other operation
+/// on a `FieldElement` can assume that invariant rather than re-checking it. Operators combine
+/// elements of different levels by embedding the lower one into the higher: `T_i` embeds into
+/// `T_j` (`i < j`) by placing the element in the low limb and zeroing the high limbs, which is
+/// exactly the padding `Parser::check_arguments` used to do by hand.
+///
+/// Levels are tracked at runtime (`level()`/`embed()`) rather than as distinct per-level types
+/// combined via a generic `Rhs`: the tower's height isn't known until a literal is parsed, so a
+/// type-level encoding would need one type per level the parser could ever produce, discovered
+/// at parse time rather than compile time. A single runtime-leveled type with `impl Add for
+/// FieldElement` (`Rhs = Self`) gets the same embed-and-combine behavior without that.
+#[derive(Clone, Debug)]
+pub struct FieldElement {
+    pub bits: Vec<bool>,
+}
+
+impl FieldElement {
+    /// Construct an element from its bitstring representation; the length must be a power of two.
+    pub fn new(bits: Vec<bool>) -> Result<Self, String> {
+        if !bits.len().is_power_of_two() {
+            return Err(format!(
+                "Bitstrings must be of length 2^i, but got length {}",
+                bits.len()
+            ));
+        }
+        Ok(FieldElement { bits })
+    }
+
+    /// The tower level `i` such that this element's bitstring has length `2^i`.
+    pub fn level(&self) -> u32 {
+        self.bits.len().ilog2()
+    }
+
+    /// Embed this element into the given tower level (which must be at least its own), placing
+    /// it in the low limb and zero-padding the high limbs.
+    pub fn embed(&self, level: u32) -> FieldElement {
+        assert!(level >= self.level());
+        let mut bits = vec![false; 1usize << level];
+        bits[..self.bits.len()].copy_from_slice(&self.bits);
+        FieldElement { bits }
+    }
+
+    /// Return the multiplicative inverse of this element (panics if it is zero).
+    pub fn inv(&self) -> FieldElement {
+        FieldElement { bits: inv(&self.bits) }
+    }
+
+    fn common_level(&self, other: &FieldElement) -> u32 {
+        max(self.level(), other.level())
+    }
+}
+
+impl PartialEq for FieldElement {
+    fn eq(&self, other: &Self) -> bool {
+        let level = self.common_level(other);
+        self.embed(level).bits == other.embed(level).bits
+    }
+}
+
+impl Add for FieldElement {
+    type Output = FieldElement;
+
+    fn add(self, rhs: FieldElement) -> FieldElement {
+        let level = self.common_level(&rhs);
+        FieldElement {
+            bits: add(&self.embed(level).bits, &rhs.embed(level).bits),
+        }
+    }
+}
+
+impl Mul for FieldElement {
+    type Output = FieldElement;
+
+    fn mul(self, rhs: FieldElement) -> FieldElement {
+        let level = self.common_level(&rhs);
+        FieldElement {
+            bits: mul(&self.embed(level).bits, &rhs.embed(level).bits),
+        }
+    }
+}
+
+impl Div for FieldElement {
+    type Output = FieldElement;
+
+    fn div(self, rhs: FieldElement) -> FieldElement {
+        let level = self.common_level(&rhs);
+        let lhs = self.embed(level);
+        let rhs = rhs.embed(level);
+        FieldElement {
+            bits: mul(&lhs.bits, &inv(&rhs.bits)),
+        }
+    }
+}
+
+#[test]
+fn test_cross_level_equality() {
+    // T0 "1" embedded in T2 is "1000"
+    let one_t0 = FieldElement::new(vec![true]).unwrap();
+    let one_t1 = FieldElement::new(vec![true, false, false, false]).unwrap();
+    assert_eq!(one_t0, one_t1);
+
+    let x0_t1 = FieldElement::new(vec![false, true, false, false]).unwrap();
+    assert_ne!(one_t0, x0_t1);
+}
+
+#[test]
+fn test_cross_level_arithmetic() {
+    // X0 (in T1) + 1 (in T0) should equal 1 + X0 in T1
+    let x0 = FieldElement::new(vec![false, true]).unwrap();
+    let one = FieldElement::new(vec![true]).unwrap();
+    let sum = x0 + one;
+    let expected = FieldElement::new(vec![true, true]).unwrap();
+    assert_eq!(sum, expected);
+}
+
+#[test]
+fn test_mul_and_div_are_inverse() {
+    let a = FieldElement::new(vec![false, false, true, false]).unwrap();
+    let b = FieldElement::new(vec![true, true, false, true]).unwrap();
+    let quotient = a.clone() * b.clone() / b;
+    assert_eq!(quotient, a);
+}
+
+#[test]
+fn test_invalid_length_rejected() {
+    assert!(FieldElement::new(vec![true, false, true]).is_err());
+}
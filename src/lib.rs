@@ -0,0 +1,12 @@
+//! Core arithmetic and parsing for the Wiedemann tower of binary fields.
+
+/// Underlying arithmetic operations
+pub mod arithmetic;
+/// Hex/base64 textual codec for tower elements
+pub mod codec;
+/// A first-class tower element with operator overloading and automatic embedding
+pub mod field_element;
+/// Parser (and evaluator) for expressions
+pub mod parser;
+/// Log/antilog lookup-table backend for small tower levels, used internally by `arithmetic`
+mod tables;
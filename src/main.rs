@@ -1,22 +1,43 @@
-/// Underlying arithmetic operations
-mod arithmetic;
-/// Parser (and evaluator) for expressions
-mod parser;
-
-use parser::Parser;
 use std::io::{self, BufRead};
 
+use wiedemann_tower::codec;
+use wiedemann_tower::field_element::FieldElement;
+use wiedemann_tower::parser::Parser;
+
+/// The radix used to print results, selected interactively with `:bin`/`:hex`/`:b64`.
+enum OutputRadix {
+    Binary,
+    Hex,
+    Base64,
+}
+
+fn print_result(result: &FieldElement, radix: &OutputRadix) {
+    match radix {
+        OutputRadix::Binary => {
+            print!("=");
+            result.bits.iter().for_each(|bit| print!("{}", if *bit { "1" } else { "0" }));
+            println!();
+        }
+        OutputRadix::Hex => println!("=0x{}", codec::encode_hex(&result.bits)),
+        OutputRadix::Base64 => println!("=b64:{}", codec::encode_base64(&result.bits)),
+    }
+}
+
 fn main() {
     let stdin = io::stdin();
-    let mut prev: Option<Vec<bool>> = None;
+    let mut prev: Option<FieldElement> = None;
+    let mut radix = OutputRadix::Binary;
 
     println!("Bitstrings represent elements of the Wiedemann tower and must be length a power of 2.");
     println!("Examples:");
     println!("T1: 00 = 0, 10 = 1, 01 = X0, 11 = 1 + X0");
     println!("T2: 0000 = 0, 1000 = 1, .., 1010 = 1 + X1, .., 1001 = 1 + X0X1");
     println!("Enter expressions using 0/1, '*', '/', '+', '()', and '_' for the previous result.");
+    println!("Literals may also be written as 0x... (hex) or b64:... (base64), optionally");
+    println!("followed by @n to give the exact bit length.");
+    println!("Use ':bin', ':hex' or ':b64' to choose the radix results are printed in.");
     println!("Type 'exit' or press Ctrl+D to quit.");
-    println!("");
+    println!();
 
     for line in stdin.lock().lines() {
         match line {
@@ -28,25 +49,25 @@ fn main() {
                 if trimmed.eq_ignore_ascii_case("exit") {
                     break;
                 }
+                match trimmed {
+                    ":bin" => {
+                        radix = OutputRadix::Binary;
+                        continue;
+                    }
+                    ":hex" => {
+                        radix = OutputRadix::Hex;
+                        continue;
+                    }
+                    ":b64" => {
+                        radix = OutputRadix::Base64;
+                        continue;
+                    }
+                    _ => {}
+                }
                 let mut parser = Parser::new(trimmed, prev.clone());
                 match parser.parse_expression() {
                     Ok(result) => {
-                        // Check if there are any leftover non-whitespace characters
-                        parser.skip_whitespace();
-                        if parser.pos < parser.chars.len() {
-                            eprintln!("Error: Unexpected character at position {}", parser.pos + 1);
-                            continue;
-                        }
-
-                        print!("=");
-                        result.iter().for_each(|bit| {
-                            if *bit {
-                                print!("1");
-                            } else {
-                                print!("0");
-                            }
-                        });
-                        println!();
+                        print_result(&result, &radix);
                         prev = Some(result);
                     }
                     Err(err_msg) => {
@@ -59,4 +80,4 @@ fn main() {
     }
 
     println!("Goodbye!");
-}
\ No newline at end of file
+}
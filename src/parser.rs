@@ -1,153 +1,255 @@
-use std::cmp::max;
+use nom::branch::alt;
+use nom::bytes::complete::{tag, take_while1};
+use nom::character::complete::{char, digit1, hex_digit1, multispace0};
+use nom::combinator::{all_consuming, cut, map, map_res, opt};
+use nom::error::{
+    context, convert_error, ContextError, ErrorKind, FromExternalError, ParseError, VerboseError,
+};
+use nom::multi::fold_many0;
+use nom::sequence::{delimited, pair, preceded};
+use nom::{Err as NomErr, IResult};
 
-use crate::arithmetic::{add, inv, mul};
+use crate::arithmetic;
+use crate::codec;
+use crate::field_element::FieldElement;
 
-/// A simple parser for bitstring expressions with AND (&) and OR (|),
-/// parentheses, and “_” as the symbol for the previous result.
+/// The error type threaded through the grammar: either a nom parse error (carrying the byte
+/// range and the set of expected tokens) or a semantic error from evaluating an otherwise
+/// well-formed expression (e.g. a malformed hex literal, or `0^0`).
+#[derive(Debug)]
+enum GrammarError<'a> {
+    Syntax(VerboseError<&'a str>),
+    Semantic(String),
+}
+
+impl<'a> ParseError<&'a str> for GrammarError<'a> {
+    fn from_error_kind(input: &'a str, kind: ErrorKind) -> Self {
+        GrammarError::Syntax(VerboseError::from_error_kind(input, kind))
+    }
+
+    fn append(input: &'a str, kind: ErrorKind, other: Self) -> Self {
+        match other {
+            GrammarError::Syntax(e) => GrammarError::Syntax(VerboseError::append(input, kind, e)),
+            semantic => semantic,
+        }
+    }
+}
+
+impl<'a> ContextError<&'a str> for GrammarError<'a> {
+    fn add_context(input: &'a str, ctx: &'static str, other: Self) -> Self {
+        match other {
+            GrammarError::Syntax(e) => GrammarError::Syntax(VerboseError::add_context(input, ctx, e)),
+            semantic => semantic,
+        }
+    }
+}
+
+impl<'a> FromExternalError<&'a str, std::num::ParseIntError> for GrammarError<'a> {
+    fn from_external_error(input: &'a str, kind: ErrorKind, _e: std::num::ParseIntError) -> Self {
+        GrammarError::Syntax(VerboseError::from_error_kind(input, kind))
+    }
+}
+
+fn semantic_err<'a, O>(message: String) -> PResult<'a, O> {
+    Err(NomErr::Failure(GrammarError::Semantic(message)))
+}
+
+type PResult<'a, O> = IResult<&'a str, O, GrammarError<'a>>;
+
+/// A parser for bitstring expressions, built from nom combinators so that operator precedence
+/// (`+`, `*`/`/`, `^`, parentheses, `_`) is expressed declaratively rather than as hand-rolled
+/// recursive descent. A failed parse reports the exact byte range and expected tokens via nom's
+/// error context, rather than a single "unexpected character" position.
+/// Bitstring literals can also be written as `0x…` (hex) or `b64:…` (base64) for readability at
+/// larger tower levels, optionally followed by `@n` to give the exact bit length explicitly (it
+/// otherwise defaults to the literal's natural length, i.e. 4 bits per hex digit, or 6 per base64
+/// digit rounded down to a whole byte).
+/// Operands may be of different tower levels: combining them is handled by `FieldElement`, which
+/// embeds the smaller into the larger rather than the parser padding bitstrings by hand.
 pub struct Parser {
-    pub chars: Vec<char>,
-    pub pos: usize,
-    pub prev: Option<Vec<bool>>,
+    input: String,
+    prev: Option<FieldElement>,
 }
 
 impl Parser {
     /// Create a new parser for the given input string, carrying the previous result.
-    pub fn new(input: &str, prev: Option<Vec<bool>>) -> Self {
+    pub fn new(input: &str, prev: Option<FieldElement>) -> Self {
         Parser {
-            chars: input.chars().collect(),
-            pos: 0,
+            input: input.to_string(),
             prev,
         }
     }
 
-    /// Parse an expression: term { '+' term }
-    pub fn parse_expression(&mut self) -> Result<Vec<bool>, String> {
-        let mut value = self.parse_term()?;
-        loop {
-            self.skip_whitespace();
-            if self.peek_char() == Some('+') {
-                self.consume_char(); // consume '+'
-                let rhs = self.parse_term()?;
-                let (lhs, rhs) = Parser::check_arguments(&value, &rhs)?;
-                value = add(&lhs, &rhs);
-            } else {
-                break;
+    /// Parse the whole input as a single expression, rejecting any unconsumed trailing
+    /// characters (other than whitespace).
+    pub fn parse_expression(&mut self) -> Result<FieldElement, String> {
+        let prev = self.prev.clone();
+        let result = all_consuming(delimited(multispace0, |i| expression(&prev, i), multispace0))(
+            self.input.as_str(),
+        );
+        match result {
+            Ok((_, value)) => Ok(value),
+            Err(NomErr::Error(GrammarError::Semantic(msg)))
+            | Err(NomErr::Failure(GrammarError::Semantic(msg))) => Err(msg),
+            Err(NomErr::Error(GrammarError::Syntax(e)))
+            | Err(NomErr::Failure(GrammarError::Syntax(e))) => {
+                Err(convert_error(self.input.as_str(), e))
             }
+            Err(NomErr::Incomplete(_)) => Err("Unexpected end of input".to_string()),
         }
-        Ok(value)
     }
+}
 
-    /// Parse a term: factor { '*|/' factor }
-    fn parse_term(&mut self) -> Result<Vec<bool>, String> {
-        let mut value = self.parse_factor()?;
-        loop {
-            self.skip_whitespace();
-            if self.peek_char() == Some('*') {
-                self.consume_char(); // consume '*'
-                let rhs = self.parse_factor()?;
-                let (lhs, rhs) = Parser::check_arguments(&value, &rhs)?;
-                value = mul(&lhs, &rhs);
-            } else if self.peek_char() == Some('/') {
-                self.consume_char(); // consume '/'
-                let rhs = self.parse_factor()?;
-                let (lhs, rhs) = Parser::check_arguments(&value, &rhs)?;
-                value = mul(&lhs, &inv(&rhs));
-            } else {
-                break;
-            }
-        }
-        Ok(value)
-    }
+/// Parse an expression: term { '+' term }
+fn expression<'a>(prev: &'a Option<FieldElement>, input: &'a str) -> PResult<'a, FieldElement> {
+    let (input, init) = term(prev, input)?;
+    fold_many0(
+        preceded(delimited(multispace0, char('+'), multispace0), |i| {
+            term(prev, i)
+        }),
+        move || init.clone(),
+        |acc, rhs| acc + rhs,
+    )(input)
+}
 
-    /// Parse a factor: bitstring | '_' | '(' expression ')'
-    fn parse_factor(&mut self) -> Result<Vec<bool>, String> {
-        self.skip_whitespace();
-        match self.peek_char() {
-            Some('(') => {
-                self.consume_char(); // consume '('
-                let inner = self.parse_expression()?;
-                self.skip_whitespace();
-                if self.peek_char() == Some(')') {
-                    self.consume_char(); // consume ')'
-                    Ok(inner)
-                } else {
-                    Err("Expected ')'".to_string())
-                }
-            }
-            Some('_') => {
-                self.consume_char(); // consume '_'
-                if let Some(prev_val) = &self.prev {
-                    Ok(prev_val.clone())
-                } else {
-                    Err("No previous result available".to_string())
-                }
-            }
-            Some(c) if c == '0' || c == '1' => {
-                let mut bits: Vec<bool> = vec![];
-                while let Some('0') | Some('1') = self.peek_char() {
-                    let bit = self.consume_char().unwrap() == '1';
-                    bits.push(bit);
-                }
-                if bits.is_empty() {
-                    Err("Expected bitstring".to_string())
-                } else {
-                    Ok(bits)
-                }
-            }
-            Some(other) => Err(format!("Unexpected character '{}'", other)),
-            None => Err("Unexpected end of input".to_string()),
-        }
-    }
+/// Parse a term: power { ('*'|'/') power }
+fn term<'a>(prev: &'a Option<FieldElement>, input: &'a str) -> PResult<'a, FieldElement> {
+    let (input, init) = power(prev, input)?;
+    fold_many0(
+        pair(
+            delimited(multispace0, alt((char('*'), char('/'))), multispace0),
+            |i| power(prev, i),
+        ),
+        move || init.clone(),
+        |acc, (op, rhs)| if op == '*' { acc * rhs } else { acc / rhs },
+    )(input)
+}
 
-    /// Skip over any whitespace characters.
-    pub fn skip_whitespace(&mut self) {
-        while let Some(c) = self.peek_char() {
-            if c.is_whitespace() {
-                self.consume_char();
-            } else {
-                break;
-            }
-        }
+/// Parse a power: factor [ '^' exponent ]
+fn power<'a>(prev: &'a Option<FieldElement>, input: &'a str) -> PResult<'a, FieldElement> {
+    let (input, base) = factor(prev, input)?;
+    let (input, exponent) = opt(preceded(
+        delimited(multispace0, char('^'), multispace0),
+        context("exponent", cut(map_res(digit1, |d: &str| d.parse::<u64>()))),
+    ))(input)?;
+    match exponent {
+        None => Ok((input, base)),
+        Some(exponent) => match arithmetic::pow(&base.bits, exponent) {
+            Ok(bits) => Ok((input, FieldElement { bits })),
+            Err(msg) => semantic_err(msg),
+        },
     }
+}
+
+/// Parse a factor: hex literal | base64 literal | bitstring | '_' | '(' expression ')'
+fn factor<'a>(prev: &'a Option<FieldElement>, input: &'a str) -> PResult<'a, FieldElement> {
+    context(
+        "expression",
+        alt((
+            hex_literal,
+            base64_literal,
+            bitstring_literal,
+            |i| prev_literal(prev, i),
+            |i| paren_expression(prev, i),
+        )),
+    )(input)
+}
+
+fn paren_expression<'a>(prev: &'a Option<FieldElement>, input: &'a str) -> PResult<'a, FieldElement> {
+    delimited(
+        char('('),
+        delimited(multispace0, |i| expression(prev, i), multispace0),
+        context("')'", cut(char(')'))),
+    )(input)
+}
 
-    /// Peek at the next character without consuming it.
-    fn peek_char(&self) -> Option<char> {
-        self.chars.get(self.pos).cloned()
+fn prev_literal<'a>(prev: &'a Option<FieldElement>, input: &'a str) -> PResult<'a, FieldElement> {
+    let (input, _) = char('_')(input)?;
+    match prev {
+        Some(value) => Ok((input, value.clone())),
+        None => semantic_err("No previous result available".to_string()),
     }
+}
 
-    /// Consume and return the next character.
-    fn consume_char(&mut self) -> Option<char> {
-        if self.pos < self.chars.len() {
-            let c = self.chars[self.pos];
-            self.pos += 1;
-            Some(c)
-        } else {
-            None
-        }
+fn bitstring_literal(input: &str) -> PResult<'_, FieldElement> {
+    map(take_while1(|c| c == '0' || c == '1'), |bits: &str| {
+        bits.chars().map(|c| c == '1').collect::<Vec<bool>>()
+    })(input)
+    .and_then(|(rest, bits)| match FieldElement::new(bits) {
+        Ok(elem) => Ok((rest, elem)),
+        Err(msg) => semantic_err(msg),
+    })
+}
+
+fn hex_literal(input: &str) -> PResult<'_, FieldElement> {
+    let (input, _) = alt((tag("0x"), tag("0X")))(input)?;
+    let (input, hex) = context("hex digits", cut(hex_digit1))(input)?;
+    let (input, bit_len) = bit_length_suffix(input)?;
+    let bit_len = bit_len.unwrap_or(hex.len() * 4);
+    match codec::decode_hex(hex, bit_len).and_then(FieldElement::new) {
+        Ok(elem) => Ok((input, elem)),
+        Err(msg) => semantic_err(msg),
     }
+}
 
-    /// Check that the two bitstrings are of length a power of two and return them padded to equal
-    /// length with trailing false bits.
-    fn check_arguments(lhs: &[bool], rhs: &[bool]) -> Result<(Vec<bool>, Vec<bool>), String> {
-        if !lhs.len().is_power_of_two() {
-            return Err(format!(
-                "Bitstrings must be of length 2^i, but LHS has length {}",
-                lhs.len(),
-            ));
-        }
-        if !rhs.len().is_power_of_two() {
-            return Err(format!(
-                "Bitstrings must be of length 2^i, but RHS has length {}",
-                rhs.len(),
-            ));
-        }
-        let max_log = max(lhs.len().ilog2(), rhs.len().ilog2());
-        let target_length = 1 << max_log; // 2^max_log
-        let mut lhs_result = vec![false; target_length];
-        let mut rhs_result = vec![false; target_length];
-        lhs_result[..lhs.len()].copy_from_slice(lhs);
-        rhs_result[..rhs.len()].copy_from_slice(rhs);
-
-        Ok((lhs_result, rhs_result))
+fn base64_literal(input: &str) -> PResult<'_, FieldElement> {
+    let (input, _) = tag("b64:")(input)?;
+    let (input, b64) = context(
+        "base64 digits",
+        cut(take_while1(|c: char| c.is_ascii_alphanumeric() || c == '+' || c == '/' || c == '=')),
+    )(input)?;
+    let (input, bit_len) = bit_length_suffix(input)?;
+    let natural_len = b64.chars().filter(|&c| c != '=').count() * 6 / 8 * 8;
+    let bit_len = bit_len.unwrap_or(natural_len);
+    match codec::decode_base64(b64, bit_len).and_then(FieldElement::new) {
+        Ok(elem) => Ok((input, elem)),
+        Err(msg) => semantic_err(msg),
     }
 }
+
+/// Parse an optional `@n` suffix giving an explicit bit length for a hex/base64 literal.
+fn bit_length_suffix(input: &str) -> PResult<'_, Option<usize>> {
+    opt(preceded(
+        char('@'),
+        context("bit length", cut(map_res(digit1, |d: &str| d.parse::<usize>()))),
+    ))(input)
+}
+
+#[test]
+fn test_operator_precedence() {
+    // '*' binds tighter than '+': 1 + 0*0 is 1 + (0*0) = 1, not (1+0)*0 = 0.
+    let mut parser = Parser::new("1 + 0*0", None);
+    assert_eq!(parser.parse_expression().unwrap().bits, vec![true]);
+}
+
+#[test]
+fn test_exponent_operator() {
+    // X_0 generates the multiplicative group of T1 (order 3), so X_0^3 = 1.
+    let mut parser = Parser::new("01^3", None);
+    assert_eq!(parser.parse_expression().unwrap().bits, vec![true, false]);
+}
+
+#[test]
+fn test_hex_literal_with_explicit_bit_length() {
+    let mut parser = Parser::new("0xf@4", None);
+    assert_eq!(
+        parser.parse_expression().unwrap().bits,
+        vec![true, true, true, true]
+    );
+}
+
+#[test]
+fn test_base64_literal_with_explicit_bit_length() {
+    let mut parser = Parser::new("b64:AQ==@8", None);
+    assert_eq!(
+        parser.parse_expression().unwrap().bits,
+        vec![true, false, false, false, false, false, false, false]
+    );
+}
+
+#[test]
+fn test_syntax_error_reports_the_offending_input() {
+    let mut parser = Parser::new("1 $", None);
+    let err = parser.parse_expression().unwrap_err();
+    assert!(err.contains('$'), "error should point at the bad input: {}", err);
+}
@@ -0,0 +1,116 @@
+//! Log/antilog lookup tables for small tower levels, turning `mul`/`inv`/`div` into array
+//! lookups instead of recursion. A table is built once per level the first time it is needed
+//! (bootstrapped using the recursive multiplication) and cached for the life of the process.
+
+use std::collections::HashMap;
+use std::sync::{Mutex, OnceLock};
+
+use crate::arithmetic::recursive_mul;
+
+/// Bitstrings of this length or shorter get a log/antilog table; `arithmetic::mul`/`inv` fall
+/// back to the recursive algorithms above it. Note this bounds the bitstring length itself
+/// (i.e. `GF(2^n)` for `n` up to this value), not the tower level `log2(n)`, since that's what
+/// `build_tables` below allocates `1 << n` entries for.
+pub const MAX_TABLED_BITS: usize = 16;
+
+struct LevelTables {
+    /// `antilog[i] = g^i` for a fixed generator `g` and `i` in `0..2^n - 1`.
+    antilog: Vec<Vec<bool>>,
+    /// `log[packed(x)] = i` such that `antilog[i] == x`, for non-zero `x`.
+    log: Vec<usize>,
+}
+
+/// The packed integer value of a bitstring, bit `i` contributing `2^i` (little-endian).
+fn packed(bits: &[bool]) -> usize {
+    bits.iter()
+        .enumerate()
+        .fold(0usize, |acc, (i, &bit)| acc | ((bit as usize) << i))
+}
+
+/// The bitstring of length `n` with the given packed integer value.
+fn unpacked(value: usize, n: usize) -> Vec<bool> {
+    (0..n).map(|i| (value >> i) & 1 == 1).collect()
+}
+
+/// Build the log/antilog tables for the level with bitstrings of length `n`, by finding a
+/// multiplicative generator and walking its powers with the recursive multiplication algorithm.
+fn build_tables(n: usize) -> LevelTables {
+    let size = 1usize << n;
+    let order = size - 1;
+    let mut one = vec![false; n];
+    one[0] = true;
+    for candidate in 2..size {
+        let g = unpacked(candidate, n);
+        let mut antilog = vec![one.clone()];
+        let mut acc = one.clone();
+        loop {
+            acc = recursive_mul(&acc, &g);
+            if acc == one {
+                break;
+            }
+            antilog.push(acc.clone());
+            if antilog.len() > order {
+                break; // g has no finite order over this field; shouldn't happen
+            }
+        }
+        if antilog.len() == order {
+            let mut log = vec![0usize; size];
+            for (i, elem) in antilog.iter().enumerate() {
+                log[packed(elem)] = i;
+            }
+            return LevelTables { antilog, log };
+        }
+    }
+    unreachable!("GF(2^{}) has a multiplicative generator", n);
+}
+
+/// Return the cached log/antilog tables for the level with bitstrings of length `n`,
+/// building and caching them on first use.
+fn tables_for_level(n: usize) -> &'static LevelTables {
+    static CACHE: OnceLock<Mutex<HashMap<usize, &'static LevelTables>>> = OnceLock::new();
+    let cache = CACHE.get_or_init(|| Mutex::new(HashMap::new()));
+    let mut cache = cache.lock().unwrap();
+    cache
+        .entry(n)
+        .or_insert_with(|| Box::leak(Box::new(build_tables(n))))
+}
+
+/// Multiply two elements of a tabled level via log/antilog lookup.
+pub fn mul(left: &[bool], right: &[bool]) -> Vec<bool> {
+    let n = left.len();
+    if !left.iter().any(|&bit| bit) || !right.iter().any(|&bit| bit) {
+        return vec![false; n];
+    }
+    let tables = tables_for_level(n);
+    let order = tables.antilog.len();
+    let i = tables.log[packed(left)];
+    let j = tables.log[packed(right)];
+    tables.antilog[(i + j) % order].clone()
+}
+
+/// Invert a non-zero element of a tabled level via log/antilog lookup.
+pub fn inv(operand: &[bool]) -> Vec<bool> {
+    let n = operand.len();
+    let tables = tables_for_level(n);
+    let order = tables.antilog.len();
+    let i = tables.log[packed(operand)];
+    tables.antilog[(order - i) % order].clone()
+}
+
+#[test]
+fn test_tabled_mul_matches_recursive() {
+    let x2 = vec![false, false, false, false, true, false, false, false];
+    assert_eq!(mul(&x2, &x2), recursive_mul(&x2, &x2));
+}
+
+#[test]
+fn test_tabled_inv_matches_recursive() {
+    let elem = vec![false, false, true, false, true, false, false, false];
+    assert_eq!(inv(&elem), crate::arithmetic::recursive_inv(&elem));
+}
+
+#[test]
+fn test_packed_roundtrip() {
+    let bits = vec![true, false, true, true];
+    assert_eq!(unpacked(packed(&bits), bits.len()), bits);
+}